@@ -53,8 +53,42 @@
 //! When using your throttle, keep in mind that you are responsible for sharing it between
 //! threads safely and responsibly.
 
+// This crate favours explicit `return`s, `assert_eq!(.., true)` in its tests, and spelling out
+// field names at construction; keep clippy from fighting that house style.
+#![allow(clippy::needless_return)]
+#![allow(clippy::bool_assert_comparison)]
+#![allow(clippy::redundant_field_names)]
+#![allow(clippy::type_complexity)]
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Condvar, Mutex};
 use std::time::{Duration, Instant};
 use std::thread::sleep;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+
+/// A runtime-agnostic asynchronous sleep, handed to [`Throttle::with_async_sleep`]. Given the delay
+/// to wait it returns a boxed future that completes once the delay has elapsed; provide one backed
+/// by whatever executor you run on (for example `tokio::time::sleep`).
+#[cfg(feature = "async")]
+type SleepProvider = Box<dyn Fn(Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+/// How a computed target instant is snapped to a time frame by
+/// [`Throttle::with_time_frame`]. Coarsening wakeups onto shared frame boundaries lets many
+/// deadlines coalesce onto the same instant, trimming scheduling overhead.
+#[derive(Copy, Clone)]
+pub enum FramePolicy {
+    /// Snap to whichever frame boundary is closest, which may release up to `granularity / 2`
+    /// earlier or later than the true deadline.
+    Nearest,
+    /// Always snap to the frame boundary at or after the true deadline, so the throttle never
+    /// releases early — at the cost of releasing up to `granularity` late. Use this when a
+    /// downstream minimum interval must not be violated.
+    AtLeast
+}
 
 #[derive(Copy, Clone)]
 enum ThrottleState {
@@ -66,8 +100,41 @@ enum ThrottleState {
 
 /// A simple configurable throttle for slowing down code, a little struct holding some state.
 pub struct Throttle<TArg> {
-    delay_calculator: Box<Fn(TArg, Duration) -> Duration>,
-    state: ThrottleState
+    delay_calculator: Box<dyn Fn(TArg, Duration) -> Duration + Send + Sync>,
+    state: ThrottleState,
+    // When present, a counting semaphore (the count of free slots, paired with a condvar to park on
+    // when none remain) capping how many callers may be inside the throttled section at once.
+    concurrency: Option<Arc<(Mutex<usize>, Condvar)>>,
+    // When present, the frame granularity and rounding policy used to snap the computed target
+    // instant so nearby deadlines coalesce onto shared frame boundaries.
+    time_frame: Option<(Duration, FramePolicy)>,
+    // A fixed reference instant that frame boundaries are measured from, so that independent
+    // acquisitions against the same throttle snap to the same boundaries rather than to offsets
+    // relative to their own previous invocation.
+    epoch: Instant,
+    // When present, the executor-agnostic sleep used by `acquire_async` in place of a blocking
+    // `std::thread::sleep`.
+    #[cfg(feature = "async")]
+    sleep_provider: Option<SleepProvider>
+}
+
+/// An RAII guard handed back by [`Throttle::acquire`] representing one occupied slot of a
+/// concurrency-limited throttle. Hold it for the duration of the throttled work; dropping it frees
+/// the slot for the next waiting caller. Throttles built without [`Throttle::with_concurrency`]
+/// hand back a permit that guards nothing.
+pub struct ThrottlePermit {
+    slot: Option<Arc<(Mutex<usize>, Condvar)>>
+}
+
+impl Drop for ThrottlePermit {
+    fn drop(&mut self) {
+        if let Some(ref semaphore) = self.slot {
+            let (available, condvar) = &**semaphore;
+            let mut available = available.lock().unwrap();
+            *available += 1;
+            condvar.notify_one();
+        }
+    }
 }
 
 impl <TArg> Throttle<TArg> {
@@ -134,11 +201,16 @@ impl <TArg> Throttle<TArg> {
     /// assert_eq!(start_yespressure.elapsed().as_secs() == 0, true);
     /// assert_eq!(start_yespressure.elapsed().subsec_nanos() >= 200_000_000, true);
     /// ```
-    pub fn new_variable_throttle<TDelayCalculator: Fn(TArg, Duration) -> Duration + 'static>(
+    pub fn new_variable_throttle<TDelayCalculator: Fn(TArg, Duration) -> Duration + Send + Sync + 'static>(
         delay_calculator: TDelayCalculator) -> Throttle<TArg> {
         return Throttle {
             delay_calculator: Box::new(delay_calculator),
-            state: ThrottleState::Uninitialized
+            state: ThrottleState::Uninitialized,
+            concurrency: None,
+            time_frame: None,
+            epoch: Instant::now(),
+            #[cfg(feature = "async")]
+            sleep_provider: None
         };
     }
 
@@ -159,49 +231,404 @@ impl <TArg> Throttle<TArg> {
     /// assert_eq!(start.elapsed().as_secs() == 1, true);
     /// ```
     pub fn new_tps_throttle(tps: f32) -> Throttle<TArg> {
-        let wait_for_millis = ((1.0 / tps) * 1000.0) as u64;
+        // A tps of zero divides to a non-finite wait; treat that (like any non-finite result) as no
+        // delay rather than letting the float-to-int cast saturate to an effectively infinite sleep.
+        let wait_millis = (1.0 / tps) * 1000.0;
+        let wait_for_millis = if wait_millis.is_finite() { wait_millis as u64 } else { 0 };
         return Throttle {
             delay_calculator: Box::new(move |_, _|
                 Duration::from_millis(wait_for_millis)),
-            state: ThrottleState::Uninitialized
+            state: ThrottleState::Uninitialized,
+            concurrency: None,
+            time_frame: None,
+            epoch: Instant::now(),
+            #[cfg(feature = "async")]
+            sleep_provider: None
         };
     }
 
+    /// Caps how many callers may be inside the throttled section simultaneously, layered on top of
+    /// whatever pacing this throttle already enforces. After construction, `acquire` additionally
+    /// blocks until one of `n` slots is free and returns a [`ThrottlePermit`] holding that slot
+    /// until it is dropped. This composes with the usual interval logic: a caller waits until both
+    /// a slot is available *and* enough time has elapsed since the previous dispatch.
+    ///
+    /// ```rust
+    /// # extern crate mysteriouspants_throttle;
+    /// # use mysteriouspants_throttle::Throttle;
+    /// // at most two concurrent requests, still spaced at 10 TPS
+    /// let mut throttle = Throttle::new_tps_throttle(10.0).with_concurrency(2);
+    ///
+    /// let _first = throttle.acquire(());
+    /// let _second = throttle.acquire(());
+    /// // a third acquire here would block until `_first` or `_second` is dropped
+    /// ```
+    pub fn with_concurrency(mut self, n: usize) -> Throttle<TArg> {
+        self.concurrency = Some(Arc::new((Mutex::new(n), Condvar::new())));
+        return self;
+    }
+
+    /// Snaps each computed target instant onto a coarse time frame of width `granularity`, so that
+    /// acquisitions sharing this throttle coalesce their wakeups onto the same frame boundaries and
+    /// spare the scheduler some churn. `policy` chooses how the snap rounds: [`FramePolicy::Nearest`]
+    /// picks the closest boundary, while [`FramePolicy::AtLeast`] always rounds up to the boundary
+    /// at or after the true deadline so the throttle never releases early.
+    ///
+    /// ```rust
+    /// # extern crate mysteriouspants_throttle;
+    /// # use std::time::Duration;
+    /// # use mysteriouspants_throttle::{FramePolicy, Throttle};
+    /// // never release early, coalescing wakeups onto 50 ms frames
+    /// let mut throttle = Throttle::new_tps_throttle(10.0)
+    ///     .with_time_frame(Duration::from_millis(50), FramePolicy::AtLeast);
+    ///
+    /// // the first one is free, later acquisitions land on 50 ms frame boundaries
+    /// throttle.acquire(());
+    /// ```
+    pub fn with_time_frame(mut self, granularity: Duration, policy: FramePolicy) -> Throttle<TArg> {
+        self.time_frame = Some((granularity, policy));
+        return self;
+    }
+
+    // Snaps `target` onto the configured time frame, if any, following the selected `FramePolicy`.
+    // The target is measured against the throttle's fixed `epoch` rather than against whatever the
+    // previous invocation was, so that independent acquisitions land on the same frame boundaries
+    // and their wakeups coalesce.
+    fn snap_to_time_frame(&self, target: Instant) -> Instant {
+        match self.time_frame {
+            Some((granularity, policy)) => {
+                let granularity_nanos = granularity.as_nanos();
+
+                if granularity_nanos == 0 {
+                    return target;
+                }
+
+                let offset_nanos = target.duration_since(self.epoch).as_nanos();
+                let remainder = offset_nanos % granularity_nanos;
+
+                if remainder == 0 {
+                    return target;
+                }
+
+                let snapped_nanos = match policy {
+                    FramePolicy::Nearest => {
+                        if remainder * 2 >= granularity_nanos {
+                            offset_nanos - remainder + granularity_nanos
+                        } else {
+                            offset_nanos - remainder
+                        }
+                    },
+                    FramePolicy::AtLeast => offset_nanos - remainder + granularity_nanos
+                };
+
+                return self.epoch + Duration::from_nanos(snapped_nanos as u64);
+            },
+            None => return target
+        }
+    }
+
     /// Acquires the throttle, waiting (sleeping the current thread) until enough time has passed
     /// for the running code to be at or slower than the throttle allows. The first call to
     /// `acquire` will never wait because there has been an undefined or arguably infinite amount
     /// of time from the previous time acquire was called. The argument `arg` is passed to the
     /// closure governing the wait time.
-    pub fn acquire(&mut self, arg: TArg) {
+    ///
+    /// The returned [`ThrottlePermit`] matters only for throttles built with
+    /// [`Throttle::with_concurrency`], where it holds a concurrency slot until dropped; for a plain
+    /// throttle it guards nothing and may be ignored.
+    pub fn acquire(&mut self, arg: TArg) -> ThrottlePermit {
+        // If this throttle is concurrency-limited, claim a slot first, parking on the condvar for
+        // as long as every slot is taken.
+        let permit = match self.concurrency {
+            Some(ref semaphore) => {
+                let (available, condvar) = &**semaphore;
+                let mut available = available.lock().unwrap();
+                while *available == 0 {
+                    available = condvar.wait(available).unwrap();
+                }
+                *available -= 1;
+                ThrottlePermit { slot: Some(semaphore.clone()) }
+            },
+            None => ThrottlePermit { slot: None }
+        };
+
         match self.state {
             ThrottleState::Initialized { previous_invocation } => {
                 let time_since_previous_acquire =
                     Instant::now().duration_since(previous_invocation);
                 let delay_time = (self.delay_calculator)(arg, time_since_previous_acquire);
+                let target = self.snap_to_time_frame(previous_invocation + delay_time);
 
-                if delay_time > Duration::from_secs(0)
-                        && delay_time > time_since_previous_acquire {
-                    let additional_delay_required = delay_time - time_since_previous_acquire;
+                let now = Instant::now();
+                if target > now {
+                    sleep(target.duration_since(now));
+                }
 
-                    if additional_delay_required > Duration::from_secs(0) {
-                        sleep(additional_delay_required);
-                    }
+                self.state = ThrottleState::Initialized { previous_invocation: Instant::now() };
+            },
+            ThrottleState::Uninitialized => {
+                self.state = ThrottleState::Initialized { previous_invocation: Instant::now() };
+            }
+        }
+
+        return permit;
+    }
+
+    /// The non-blocking counterpart to [`acquire`](Throttle::acquire). Runs the same delay
+    /// computation but, rather than sleeping, reports whether the throttle is ready. When more time
+    /// must elapse it returns `Err(remaining)` — the still-outstanding wait — and leaves the
+    /// throttle's state untouched so a later retry sees the same deadline. When the throttle is
+    /// ready it returns `Ok(())`, updating state exactly as `acquire` does. As with `acquire`, the
+    /// first call is always free.
+    ///
+    /// This is the primitive for event loops and single-threaded schedulers that cannot afford to
+    /// block the thread: spin, do other work, or arm a timer for `remaining` instead of sleeping.
+    /// Unlike `acquire`, it does not participate in [`with_concurrency`](Throttle::with_concurrency)
+    /// slotting.
+    ///
+    /// ```rust
+    /// # extern crate mysteriouspants_throttle;
+    /// # use std::time::Duration;
+    /// # use mysteriouspants_throttle::Throttle;
+    /// let mut throttle = Throttle::new_tps_throttle(10.0);
+    ///
+    /// // the first one is free
+    /// assert!(throttle.try_acquire(()).is_ok());
+    ///
+    /// // immediately trying again is too soon
+    /// assert!(throttle.try_acquire(()).is_err());
+    /// ```
+    pub fn try_acquire(&mut self, arg: TArg) -> Result<(), Duration> {
+        match self.state {
+            ThrottleState::Initialized { previous_invocation } => {
+                let time_since_previous_acquire =
+                    Instant::now().duration_since(previous_invocation);
+                let delay_time = (self.delay_calculator)(arg, time_since_previous_acquire);
+                let target = self.snap_to_time_frame(previous_invocation + delay_time);
+
+                let now = Instant::now();
+                if target > now {
+                    return Err(target.duration_since(now));
                 }
 
                 self.state = ThrottleState::Initialized { previous_invocation: Instant::now() };
+                return Ok(());
             },
             ThrottleState::Uninitialized => {
                 self.state = ThrottleState::Initialized { previous_invocation: Instant::now() };
+                return Ok(());
             }
         }
     }
+
+    /// Installs the executor-agnostic `sleep_provider` used by
+    /// [`acquire_async`](Throttle::acquire_async). It is handed the `Duration` still to wait and
+    /// must return a future that completes once that long has elapsed, so the throttle stays
+    /// independent of any particular async runtime.
+    ///
+    /// ```rust,ignore
+    /// let throttle = Throttle::new_tps_throttle(10.0)
+    ///     .with_async_sleep(|d| tokio::time::sleep(d));
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn with_async_sleep<TFuture, TSleepProvider>(
+        mut self, sleep_provider: TSleepProvider) -> Throttle<TArg>
+        where TFuture: Future<Output = ()> + Send + 'static,
+              TSleepProvider: Fn(Duration) -> TFuture + Send + Sync + 'static {
+        self.sleep_provider = Some(Box::new(move |delay| Box::pin(sleep_provider(delay))));
+        return self;
+    }
+
+    /// The asynchronous counterpart to [`acquire`](Throttle::acquire), for use inside async servers
+    /// where blocking an OS thread with `sleep` is unacceptable. It runs the same delay computation
+    /// as the synchronous path and then awaits the [`with_async_sleep`](Throttle::with_async_sleep)
+    /// provider for the outstanding `Duration` instead of sleeping the thread. As ever the first
+    /// call is free, and a computed delay of zero completes immediately without suspending. Note
+    /// that unlike `acquire` it does not participate in
+    /// [`with_concurrency`](Throttle::with_concurrency) slotting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a non-zero wait is required but the throttle was not built with a sleep provider
+    /// via [`with_async_sleep`](Throttle::with_async_sleep); without one there is no way to wait,
+    /// and silently returning would defeat the pacing the caller asked for.
+    #[cfg(feature = "async")]
+    pub async fn acquire_async(&mut self, arg: TArg) {
+        let additional_delay_required = match self.state {
+            ThrottleState::Initialized { previous_invocation } => {
+                let time_since_previous_acquire =
+                    Instant::now().duration_since(previous_invocation);
+                let delay_time = (self.delay_calculator)(arg, time_since_previous_acquire);
+                let target = self.snap_to_time_frame(previous_invocation + delay_time);
+
+                let now = Instant::now();
+                if target > now {
+                    target.duration_since(now)
+                } else {
+                    Duration::from_secs(0)
+                }
+            },
+            ThrottleState::Uninitialized => Duration::from_secs(0)
+        };
+
+        // A zero delay must resolve immediately without ever suspending the task.
+        if additional_delay_required > Duration::from_secs(0) {
+            match self.sleep_provider {
+                Some(ref sleep_provider) => {
+                    (sleep_provider)(additional_delay_required).await;
+                },
+                // Silently returning here would defeat pacing entirely, so treat a missing provider
+                // as the misconfiguration it is.
+                None => panic!(
+                    "acquire_async requires a sleep provider; build the Throttle with \
+                     with_async_sleep before calling it")
+            }
+        }
+
+        self.state = ThrottleState::Initialized { previous_invocation: Instant::now() };
+    }
+}
+
+/// A keyed collection of `Throttle`s, one per resource, created lazily on first use. This is handy
+/// when you need to pace calls to many distinct downstream targets — say one per hostname, IP, or
+/// API endpoint — without hand-rolling a `Throttle` per target. Acquiring against one key never
+/// blocks acquisition against another, so each resource gets its own independent pacing.
+///
+/// The pool is `Send` and `Sync`, so the usual pattern is to wrap it in an `Arc` and share it
+/// across worker threads.
+///
+/// ```rust
+/// # extern crate mysteriouspants_throttle;
+/// # use std::sync::Arc;
+/// # use mysteriouspants_throttle::{Throttle, ThrottlePool};
+/// // ten transactions per second, per host
+/// let pool: ThrottlePool<String, ()> =
+///     ThrottlePool::new(|_host: &String| Throttle::new_tps_throttle(10.0));
+/// let pool = Arc::new(pool);
+///
+/// pool.acquire("example.com".to_string(), ());
+/// pool.acquire("example.org".to_string(), ());
+/// ```
+pub struct ThrottlePool<TKey, TArg> {
+    throttles: Mutex<HashMap<TKey, Arc<Mutex<Throttle<TArg>>>>>,
+    throttle_factory: Box<dyn Fn(&TKey) -> Throttle<TArg> + Send + Sync>
+}
+
+impl <TKey: Eq + Hash + Clone, TArg> ThrottlePool<TKey, TArg> {
+    /// Creates a new `ThrottlePool`. The `throttle_factory` is called the first time a key is
+    /// acquired against to build that key's `Throttle`; thereafter the cached `Throttle` is reused.
+    /// The key is handed to the factory by reference so it may influence the throttle it builds.
+    pub fn new<TThrottleFactory: Fn(&TKey) -> Throttle<TArg> + Send + Sync + 'static>(
+        throttle_factory: TThrottleFactory) -> ThrottlePool<TKey, TArg> {
+        return ThrottlePool {
+            throttles: Mutex::new(HashMap::new()),
+            throttle_factory: Box::new(throttle_factory)
+        };
+    }
+
+    /// Acquires the throttle belonging to `key`, constructing it from the factory on first use. The
+    /// wait — if any — happens while holding only that key's lock, so acquisitions against other
+    /// keys remain free to proceed. `arg` is forwarded to the underlying `Throttle::acquire`.
+    pub fn acquire(&self, key: TKey, arg: TArg) {
+        // Only hold the shared map lock long enough to look up (or create) this key's throttle,
+        // then clone the handle out so the sleep in acquire never blocks the other keys.
+        let throttle = {
+            let mut throttles = self.throttles.lock().unwrap();
+            throttles.entry(key.clone())
+                .or_insert_with(|| Arc::new(Mutex::new((self.throttle_factory)(&key))))
+                .clone()
+        };
+
+        throttle.lock().unwrap().acquire(arg);
+    }
+}
+
+/// An iterator adapter that paces the items of an underlying iterator through a [`Throttle`],
+/// yielded by [`ThrottleIteratorExt::throttle`] and friends. Each call to `next` acquires the
+/// throttle before handing back the item, so — following the usual "first one is free" rule — the
+/// first item is emitted immediately and the rest are spaced out. This turns a throttle into a
+/// drop-in pacing combinator for a data pipeline.
+pub struct ThrottledIterator<TIterator, TArg, TArgFn> {
+    iterator: TIterator,
+    throttle: Throttle<TArg>,
+    arg_calculator: TArgFn
+}
+
+impl <TIterator, TArg, TArgFn> Iterator for ThrottledIterator<TIterator, TArg, TArgFn>
+    where TIterator: Iterator,
+          TArgFn: Fn(&TIterator::Item) -> TArg {
+    type Item = TIterator::Item;
+
+    fn next(&mut self) -> Option<TIterator::Item> {
+        match self.iterator.next() {
+            Some(item) => {
+                let arg = (self.arg_calculator)(&item);
+                self.throttle.acquire(arg);
+                return Some(item);
+            },
+            None => return None
+        }
+    }
+}
+
+/// Extension trait adding throttling combinators to any [`Iterator`]. Import it to gain
+/// [`throttle`](ThrottleIteratorExt::throttle) and
+/// [`throttle_tps`](ThrottleIteratorExt::throttle_tps) on iterators.
+///
+/// ```rust
+/// # extern crate mysteriouspants_throttle;
+/// # use std::time::Instant;
+/// # use mysteriouspants_throttle::ThrottleIteratorExt;
+/// let iteration_start = Instant::now();
+///
+/// // pace a range at 10 TPS; eleven items should take just over a second
+/// for _item in (0..11).throttle_tps(10.0) {
+///   // do the needful
+/// }
+///
+/// assert_eq!(iteration_start.elapsed().as_secs() == 1, true);
+/// ```
+pub trait ThrottleIteratorExt: Iterator + Sized {
+    /// Paces this iterator through `throttle`, deriving the `acquire` argument from each yielded
+    /// item via `arg_calculator`. Use this with a variable-rate throttle whose delay depends on the
+    /// item being emitted.
+    fn throttle<TArg, TArgFn: Fn(&Self::Item) -> TArg>(
+        self, throttle: Throttle<TArg>, arg_calculator: TArgFn)
+        -> ThrottledIterator<Self, TArg, TArgFn>;
+
+    /// Paces this iterator at a constant `tps` transactions per second, a shorthand for pairing
+    /// [`throttle`](ThrottleIteratorExt::throttle) with [`Throttle::new_tps_throttle`] and an
+    /// argument that is ignored.
+    fn throttle_tps(self, tps: f32)
+        -> ThrottledIterator<Self, (), fn(&Self::Item) -> ()>;
+}
+
+impl <TIterator: Iterator> ThrottleIteratorExt for TIterator {
+    fn throttle<TArg, TArgFn: Fn(&Self::Item) -> TArg>(
+        self, throttle: Throttle<TArg>, arg_calculator: TArgFn)
+        -> ThrottledIterator<Self, TArg, TArgFn> {
+        return ThrottledIterator {
+            iterator: self,
+            throttle: throttle,
+            arg_calculator: arg_calculator
+        };
+    }
+
+    fn throttle_tps(self, tps: f32)
+        -> ThrottledIterator<Self, (), fn(&Self::Item) -> ()> {
+        let arg_calculator: fn(&Self::Item) -> () = |_| ();
+        return self.throttle(Throttle::new_tps_throttle(tps), arg_calculator);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::time::{Duration, Instant};
     use std::thread::sleep;
-    use Throttle;
+    use crate::FramePolicy;
+    use crate::Throttle;
+    use crate::ThrottlePool;
+    use crate::ThrottleIteratorExt;
 
     #[test]
     fn it_works() {
@@ -272,4 +699,182 @@ mod tests {
 
         // no panic, no problem!
     }
+
+    #[test]
+    fn pool_paces_each_key_independently() {
+        let pool: ThrottlePool<u8, ()> =
+            ThrottlePool::new(|_key: &u8| Throttle::new_tps_throttle(10.0));
+
+        // the first acquire against each key is free
+        let first_start = Instant::now();
+        pool.acquire(1, ());
+        pool.acquire(2, ());
+
+        // key 2 has its own throttle, so it did not wait out key 1's 100 ms interval
+        assert_eq!(first_start.elapsed() < Duration::from_millis(100), true);
+
+        let iteration_start = Instant::now();
+
+        // ten more against key 1, which at 10 TPS should take about a second
+        for _i in 0..10 {
+            pool.acquire(1, ());
+        }
+
+        assert_eq!(iteration_start.elapsed().as_secs() == 1, true);
+    }
+
+    #[test]
+    fn concurrency_permits_gate_the_second_caller() {
+        use std::sync::{Arc, Mutex};
+        use std::thread;
+
+        // a single slot, paced fast enough that only the concurrency limit is in play
+        let throttle = Arc::new(Mutex::new(
+            Throttle::new_tps_throttle(1000.0).with_concurrency(1)));
+
+        // hold the only slot
+        let permit = throttle.lock().unwrap().acquire(());
+
+        let background = {
+            let throttle = throttle.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                let _permit = throttle.lock().unwrap().acquire(());
+                start.elapsed()
+            })
+        };
+
+        // give the background thread time to block on the slot, then release it
+        sleep(Duration::from_millis(100));
+        drop(permit);
+
+        // the background acquire only completed once we dropped our permit
+        assert_eq!(background.join().unwrap().subsec_millis() >= 100, true);
+    }
+
+    #[test]
+    fn iterator_adapter_paces_at_tps() {
+        let iteration_start = Instant::now();
+
+        let collected: Vec<u64> = (0..11).throttle_tps(10.0).collect();
+
+        assert_eq!(collected.len(), 11);
+        assert_eq!(iteration_start.elapsed().as_secs() == 1, true);
+    }
+
+    #[test]
+    fn iterator_adapter_derives_arg_for_variable_throttle() {
+        let throttle = Throttle::new_variable_throttle(
+            |arg: u64, _| Duration::from_millis(arg));
+
+        let iteration_start = Instant::now();
+
+        let collected: Vec<u64> =
+            (0..5).throttle(throttle, |item: &u64| item * 100).collect();
+
+        assert_eq!(collected.len(), 5);
+        assert_eq!(iteration_start.elapsed().as_secs() == 1, true);
+    }
+
+    #[test]
+    fn try_acquire_reports_remaining_without_blocking() {
+        // iterate every 100 ms
+        let mut throttle = Throttle::new_tps_throttle(10.0);
+
+        // the first one is free
+        assert_eq!(throttle.try_acquire(()).is_ok(), true);
+
+        // too soon — we should be told roughly how long is left, with no sleeping
+        match throttle.try_acquire(()) {
+            Ok(()) => panic!("expected the throttle to report a remaining wait"),
+            Err(remaining) => assert_eq!(remaining <= Duration::from_millis(100), true)
+        }
+
+        // a failed try must not advance the deadline
+        assert_eq!(throttle.try_acquire(()).is_err(), true);
+
+        // once enough time has passed it is ready again
+        sleep(Duration::from_millis(100));
+        assert_eq!(throttle.try_acquire(()).is_ok(), true);
+    }
+
+    #[test]
+    fn try_acquire_honours_the_time_frame() {
+        // 100 ms interval snapped up onto 80 ms frames lands the deadline at 160 ms
+        let mut throttle = Throttle::new_tps_throttle(10.0)
+            .with_time_frame(Duration::from_millis(80), FramePolicy::AtLeast);
+
+        // the first one is free
+        assert_eq!(throttle.try_acquire(()).is_ok(), true);
+
+        // past the raw 100 ms deadline but short of the snapped 160 ms frame, so still not ready —
+        // the non-blocking path agrees with the blocking one on when the throttle releases
+        sleep(Duration::from_millis(110));
+        assert_eq!(throttle.try_acquire(()).is_err(), true);
+
+        // and once the frame boundary has passed it is ready
+        sleep(Duration::from_millis(60));
+        assert_eq!(throttle.try_acquire(()).is_ok(), true);
+    }
+
+    #[test]
+    fn time_frame_at_least_never_releases_early() {
+        // a 100 ms interval snapped onto 80 ms frames rounds up to the next frame boundary
+        let mut throttle = Throttle::new_tps_throttle(10.0)
+            .with_time_frame(Duration::from_millis(80), FramePolicy::AtLeast);
+
+        // the first one is free
+        throttle.acquire(());
+
+        let start = Instant::now();
+        throttle.acquire(());
+        let elapsed = start.elapsed();
+
+        // AtLeast must never release before the true 100 ms deadline, and lands on the frame at or
+        // after it rather than wandering arbitrarily far past
+        assert_eq!(elapsed >= Duration::from_millis(100), true);
+        assert_eq!(elapsed <= Duration::from_millis(220), true);
+    }
+
+    // A bare-bones blocking executor so the async path can be exercised without pulling in a
+    // runtime dependency; it simply busy-polls the future to completion.
+    #[cfg(feature = "async")]
+    fn block_on<TFuture: ::std::future::Future>(mut future: TFuture) -> TFuture::Output {
+        use std::pin::Pin;
+        use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+        fn noop(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker { RawWaker::new(::std::ptr::null(), &VTABLE) }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        let waker = unsafe { Waker::from_raw(RawWaker::new(::std::ptr::null(), &VTABLE)) };
+        let mut context = Context::from_waker(&waker);
+        let mut future = unsafe { Pin::new_unchecked(&mut future) };
+
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => sleep(Duration::from_millis(1))
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn acquire_async_awaits_the_sleep_provider() {
+        let mut throttle = Throttle::new_tps_throttle(10.0)
+            .with_async_sleep(|delay| async move {
+                // stand in for a runtime timer
+                sleep(delay);
+            });
+
+        block_on(async {
+            // the first one is free
+            throttle.acquire_async(()).await;
+
+            let start = Instant::now();
+            throttle.acquire_async(()).await;
+            assert_eq!(start.elapsed() >= Duration::from_millis(100), true);
+        });
+    }
 }